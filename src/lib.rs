@@ -17,27 +17,70 @@
 //! extern crate mixed_ref;
 //! # fn main() {}
 //! ```
+//!
+//! # Features
+//!
+//! This crate is `no_std` by default. The `std` feature (off by default) pulls
+//! in `std::boxed::Box` and enables the `Owned` variant of [`MixedRef`] and
+//! [`MixedRefMut`]; the `alloc` feature does the same for `no_std` targets
+//! that still have a global allocator. Without either feature, both enums
+//! compile down to just their `Borrowed` variant, so the crate works on
+//! bare-metal targets that have no allocator at all.
+//!
+//! # `Into` Parameters
+//!
+//! Because every owned representation has a matching `From` impl, APIs that
+//! accept a [`MixedRef`] are most flexible when they're generic over
+//! `impl Into<MixedRef<'a, T>>` rather than a concrete `MixedRef`. This lets
+//! callers pass a `&T`, and (with the `std`/`alloc` feature) a `Box<T>`,
+//! `String`, or `Vec<T>`, all without the caller constructing the enum
+//! themselves:
+//!
+//! ```
+//! # extern crate mixed_ref;
+//! use mixed_ref::MixedRef;
+//!
+//! fn greeting<'a>(name: impl Into<MixedRef<'a, str>>) -> MixedRef<'a, str> {
+//!     name.into()
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(&*greeting("Ferris"), "Ferris");
+//! # }
+//! ```
+//!
+//! [`MixedRef`]: enum.MixedRef.html
+//! [`MixedRefMut`]: enum.MixedRefMut.html
 #![cfg_attr(not(feature = "std"), no_std)]
-#![cfg_attr(not(feature = "std"), feature(alloc))]
 
 #[cfg(feature = "std")]
 extern crate core;
 
-#[cfg(not(feature = "std"))]
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
 extern crate alloc;
 
-#[cfg(not(feature = "std"))]
-use alloc::borrow::Cow;
-#[cfg(not(feature = "std"))]
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::borrow::{Cow, ToOwned};
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
 use alloc::boxed::Box;
-#[cfg(not(feature = "std"))]
-use alloc::{String, Vec};
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::string::String;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
 
 #[cfg(feature = "std")]
 use std::borrow::Cow;
 
 use core::ops::{Deref, DerefMut};
 use core::borrow::{Borrow, BorrowMut};
+use core::cmp::Ordering;
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::ops::{Add, AddAssign};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::{fmt, mem};
+#[cfg(any(feature = "std", feature = "alloc"))]
+use core::hash::{Hash, Hasher};
 
 /// A reference to either owned or borrowed data.
 ///
@@ -50,6 +93,7 @@ use core::borrow::{Borrow, BorrowMut};
 #[derive(Debug, Eq, Hash)]
 pub enum MixedRef<'a, T: ?Sized + 'a> {
     /// Owned, boxed data.
+    #[cfg(any(feature = "std", feature = "alloc"))]
     Owned(Box<T>),
     /// Borrowed data.
     Borrowed(&'a T)
@@ -63,6 +107,7 @@ pub enum MixedRef<'a, T: ?Sized + 'a> {
 #[derive(Debug, Eq, Hash)]
 pub enum MixedRefMut<'a, T: ?Sized + 'a> {
     /// Owned, boxed data.
+    #[cfg(any(feature = "std", feature = "alloc"))]
     Owned(Box<T>),
     /// Borrowed, mutable data.
     Borrowed(&'a mut T)
@@ -80,12 +125,59 @@ impl<'a, T: ?Sized + AsRef<U>, U: ?Sized + PartialEq> PartialEq<T> for MixedRefM
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Clone for MixedRef<'a, T>
+    where T::Owned: Into<Box<T>>
+{
+    fn clone(&self) -> Self {
+        match *self {
+            MixedRef::Owned(ref b) => MixedRef::Owned((**b).to_owned().into()),
+            MixedRef::Borrowed(r) => MixedRef::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+impl<'a, T: ?Sized> Clone for MixedRef<'a, T> {
+    fn clone(&self) -> Self {
+        match *self {
+            MixedRef::Borrowed(r) => MixedRef::Borrowed(r),
+        }
+    }
+}
+
+impl<'a, T: ?Sized + Ord> Ord for MixedRef<'a, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self as &T).cmp(other as &T)
+    }
+}
+
+impl<'a, T: ?Sized + Ord> Ord for MixedRefMut<'a, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self as &T).cmp(other as &T)
+    }
+}
+
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized + PartialOrd> PartialOrd<T> for MixedRef<'a, U> {
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        (self as &U).partial_cmp(other.as_ref())
+    }
+}
+
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized + PartialOrd> PartialOrd<T> for MixedRefMut<'a, U> {
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        (self as &U).partial_cmp(other.as_ref())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: Default> Default for MixedRef<'a, T> {
     fn default() -> Self {
         MixedRef::Owned(Default::default())
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: Default> Default for MixedRefMut<'a, T> {
     fn default() -> Self {
         MixedRefMut::Owned(Default::default())
@@ -104,36 +196,42 @@ impl<'a, T: ?Sized> From<&'a mut T> for MixedRefMut<'a, T> {
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: ?Sized> From<Box<T>> for MixedRef<'a, T> {
     fn from(b: Box<T>) -> Self {
         MixedRef::Owned(b)
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: ?Sized> From<Box<T>> for MixedRefMut<'a, T> {
     fn from(b: Box<T>) -> Self {
         MixedRefMut::Owned(b)
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a> From<String> for MixedRef<'a, str> {
     fn from(s: String) -> Self {
         Self::from(s.into_boxed_str())
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a> From<String> for MixedRefMut<'a, str> {
     fn from(s: String) -> Self {
         Self::from(s.into_boxed_str())
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T> From<Vec<T>> for MixedRef<'a, [T]> {
     fn from(v: Vec<T>) -> Self {
         Self::from(v.into_boxed_slice())
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T> From<Vec<T>> for MixedRefMut<'a, [T]> {
     fn from(v: Vec<T>) -> Self {
         Self::from(v.into_boxed_slice())
@@ -143,6 +241,7 @@ impl<'a, T> From<Vec<T>> for MixedRefMut<'a, [T]> {
 impl<'a, T: ?Sized> From<MixedRefMut<'a, T>> for MixedRef<'a, T> {
     fn from(r: MixedRefMut<'a, T>) -> Self {
         match r {
+            #[cfg(any(feature = "std", feature = "alloc"))]
             MixedRefMut::Owned(b) => MixedRef::Owned(b),
             MixedRefMut::Borrowed(r) => MixedRef::Borrowed(r),
         }
@@ -154,6 +253,7 @@ impl<'a, T: ?Sized> Deref for MixedRef<'a, T> {
 
     fn deref(&self) -> &T {
         match *self {
+            #[cfg(any(feature = "std", feature = "alloc"))]
             MixedRef::Owned(ref b) => b,
             MixedRef::Borrowed(ref r) => r
         }
@@ -165,6 +265,7 @@ impl<'a, T: ?Sized> Deref for MixedRefMut<'a, T> {
 
     fn deref(&self) -> &T {
         match *self {
+            #[cfg(any(feature = "std", feature = "alloc"))]
             MixedRefMut::Owned(ref b) => b,
             MixedRefMut::Borrowed(ref r) => r
         }
@@ -174,6 +275,7 @@ impl<'a, T: ?Sized> Deref for MixedRefMut<'a, T> {
 impl<'a, T: ?Sized> DerefMut for MixedRefMut<'a, T> {
     fn deref_mut(&mut self) -> &mut T {
         match *self {
+            #[cfg(any(feature = "std", feature = "alloc"))]
             MixedRefMut::Owned(ref mut b) => b,
             MixedRefMut::Borrowed(ref mut r) => r
         }
@@ -204,6 +306,7 @@ impl<'a, T: ?Sized> BorrowMut<T> for MixedRefMut<'a, T> {
     fn borrow_mut(&mut self) -> &mut T { self }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: ?Sized + ToOwned> From<Cow<'a, T>> for MixedRef<'a, T>
     where T::Owned: Into<Box<T>>
 {
@@ -215,6 +318,7 @@ impl<'a, T: ?Sized + ToOwned> From<Cow<'a, T>> for MixedRef<'a, T>
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: ?Sized + ToOwned> Into<Cow<'a, T>> for MixedRef<'a, T>
     where Box<T>: Into<T::Owned>
 {
@@ -226,6 +330,7 @@ impl<'a, T: ?Sized + ToOwned> Into<Cow<'a, T>> for MixedRef<'a, T>
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<'a, T: ?Sized + ToOwned> Into<Cow<'a, T>> for MixedRefMut<'a, T>
     where Box<T>: Into<T::Owned>
 {
@@ -238,3 +343,758 @@ impl<'a, T: ?Sized> MixedRefMut<'a, T> {
         self.into()
     }
 }
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> MixedRef<'a, T>
+    where T::Owned: Into<Box<T>>
+{
+    /// Returns a mutable reference to the owned data, cloning the borrowed
+    /// data into an owned value in place if necessary.
+    ///
+    /// This is analogous to [`Cow::to_mut`].
+    ///
+    /// [`Cow::to_mut`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.to_mut
+    pub fn to_mut(&mut self) -> &mut T {
+        if let MixedRef::Borrowed(r) = *self {
+            *self = MixedRef::Owned(r.to_owned().into());
+        }
+        match *self {
+            MixedRef::Owned(ref mut b) => b,
+            MixedRef::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// Extracts the owned data, cloning the borrowed data if necessary.
+    ///
+    /// This is analogous to [`Cow::into_owned`].
+    ///
+    /// [`Cow::into_owned`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.into_owned
+    pub fn into_owned(self) -> Box<T> {
+        match self {
+            MixedRef::Owned(b) => b,
+            MixedRef::Borrowed(r) => r.to_owned().into(),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> MixedRefMut<'a, T>
+    where T::Owned: Into<Box<T>>
+{
+    /// Extracts the owned data, cloning the borrowed data if necessary.
+    ///
+    /// This is analogous to [`Cow::into_owned`].
+    ///
+    /// [`Cow::into_owned`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.into_owned
+    pub fn into_owned(self) -> Box<T> {
+        match self {
+            MixedRefMut::Owned(b) => b,
+            MixedRefMut::Borrowed(r) => r.to_owned().into(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> MixedRef<'a, T> {
+    /// Returns `true` if `self` holds owned data.
+    pub fn is_owned(&self) -> bool {
+        match *self {
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            MixedRef::Owned(_) => true,
+            MixedRef::Borrowed(_) => false,
+        }
+    }
+
+    /// Returns `true` if `self` holds borrowed data.
+    pub fn is_borrowed(&self) -> bool {
+        !self.is_owned()
+    }
+}
+
+impl<'a, T: ?Sized> MixedRefMut<'a, T> {
+    /// Returns `true` if `self` holds owned data.
+    pub fn is_owned(&self) -> bool {
+        match *self {
+            #[cfg(any(feature = "std", feature = "alloc"))]
+            MixedRefMut::Owned(_) => true,
+            MixedRefMut::Borrowed(_) => false,
+        }
+    }
+
+    /// Returns `true` if `self` holds borrowed data.
+    pub fn is_borrowed(&self) -> bool {
+        !self.is_owned()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> AddAssign<&str> for MixedRef<'a, str> {
+    fn add_assign(&mut self, rhs: &str) {
+        match *self {
+            MixedRef::Owned(ref mut b) => {
+                let mut owned = String::from(mem::replace(b, Box::from("")));
+                owned.push_str(rhs);
+                *b = owned.into_boxed_str();
+            }
+            MixedRef::Borrowed(s) => {
+                let mut owned = String::with_capacity(s.len() + rhs.len());
+                owned.push_str(s);
+                owned.push_str(rhs);
+                *self = MixedRef::Owned(owned.into_boxed_str());
+            }
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> AddAssign<String> for MixedRef<'a, str> {
+    fn add_assign(&mut self, rhs: String) {
+        *self += rhs.as_str();
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> Add<&str> for MixedRef<'a, str> {
+    type Output = MixedRef<'a, str>;
+
+    fn add(mut self, rhs: &str) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> Add<String> for MixedRef<'a, str> {
+    type Output = MixedRef<'a, str>;
+
+    fn add(mut self, rhs: String) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> fmt::Write for MixedRef<'a, str> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        *self += s;
+        Ok(())
+    }
+}
+
+/// A reference to either owned or borrowed data, storing the owned data in
+/// its idiomatic, non-boxed representation (e.g. `String` rather than
+/// `Box<str>`).
+///
+/// Unlike [`MixedRef`], which always boxes owned data, `MixedCow` keeps the
+/// owned value in its natural, resizable form, at the cost of being a little
+/// larger than a single pointer.
+///
+/// If mutably borrowing data, use [`MixedCowMut`].
+///
+/// [`MixedRef`]: enum.MixedRef.html
+/// [`MixedCowMut`]: enum.MixedCowMut.html
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub enum MixedCow<'a, T: ?Sized + 'a + ToOwned> {
+    /// Owned data.
+    Owned(T::Owned),
+    /// Borrowed data.
+    Borrowed(&'a T),
+}
+
+/// A reference to either owned or mutably borrowed data, storing the owned
+/// data in its idiomatic, non-boxed representation.
+///
+/// This acts similarly to [`MixedCow`], except that the inner data is
+/// mutable.
+///
+/// [`MixedCow`]: enum.MixedCow.html
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub enum MixedCowMut<'a, T: ?Sized + 'a + ToOwned> {
+    /// Owned data.
+    Owned(T::Owned),
+    /// Borrowed, mutable data.
+    Borrowed(&'a mut T),
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> fmt::Debug for MixedCow<'a, T>
+    where T: fmt::Debug, T::Owned: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MixedCow::Owned(ref o) => f.debug_tuple("Owned").field(o).finish(),
+            MixedCow::Borrowed(r) => f.debug_tuple("Borrowed").field(&r).finish(),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> fmt::Debug for MixedCowMut<'a, T>
+    where T: fmt::Debug, T::Owned: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MixedCowMut::Owned(ref o) => f.debug_tuple("Owned").field(o).finish(),
+            MixedCowMut::Borrowed(ref r) => f.debug_tuple("Borrowed").field(r).finish(),
+        }
+    }
+}
+
+// `Eq` relies on the `PartialEq<T> for MixedCow<U>`/`MixedCowMut<U>` impls
+// below, which already compare through `Deref` rather than structurally, so
+// no bound on `T::Owned` is needed here.
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned + Eq> Eq for MixedCow<'a, T> {}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned + Eq> Eq for MixedCowMut<'a, T> {}
+
+// Hashes only the dereferenced `T`, with no variant discriminant, so that
+// values which compare equal via the `Deref`-based `PartialEq` below also
+// hash equally -- required for `MixedCow` to be usable as a `HashMap`/
+// `HashSet` key across both variants.
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned + Hash> Hash for MixedCow<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self as &T).hash(state);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned + Hash> Hash for MixedCowMut<'a, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self as &T).hash(state);
+    }
+}
+
+/// This mirrors [`std::borrow::Cow`]'s `Clone` impl: owned data is re-cloned
+/// by borrowing it as `&T` and calling [`ToOwned::to_owned`] rather than
+/// requiring `T::Owned: Clone`, while borrowed data just copies the reference.
+///
+/// [`std::borrow::Cow`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Clone for MixedCow<'a, T> {
+    fn clone(&self) -> Self {
+        match *self {
+            MixedCow::Owned(ref o) => MixedCow::Owned(o.borrow().to_owned()),
+            MixedCow::Borrowed(r) => MixedCow::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Default for MixedCow<'a, T>
+    where T::Owned: Default
+{
+    fn default() -> Self {
+        MixedCow::Owned(Default::default())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Default for MixedCowMut<'a, T>
+    where T::Owned: Default
+{
+    fn default() -> Self {
+        MixedCowMut::Owned(Default::default())
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<&'a T> for MixedCow<'a, T> {
+    fn from(r: &'a T) -> Self {
+        MixedCow::Borrowed(r)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<&'a mut T> for MixedCowMut<'a, T> {
+    fn from(r: &'a mut T) -> Self {
+        MixedCowMut::Borrowed(r)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<MixedCowMut<'a, T>> for MixedCow<'a, T> {
+    fn from(c: MixedCowMut<'a, T>) -> Self {
+        match c {
+            MixedCowMut::Owned(o) => MixedCow::Owned(o),
+            MixedCowMut::Borrowed(r) => MixedCow::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<MixedRef<'a, T>> for MixedCow<'a, T>
+    where Box<T>: Into<T::Owned>
+{
+    fn from(r: MixedRef<'a, T>) -> Self {
+        match r {
+            MixedRef::Owned(b) => MixedCow::Owned(b.into()),
+            MixedRef::Borrowed(r) => MixedCow::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<MixedCow<'a, T>> for MixedRef<'a, T>
+    where T::Owned: Into<Box<T>>
+{
+    fn from(c: MixedCow<'a, T>) -> Self {
+        match c {
+            MixedCow::Owned(o) => MixedRef::Owned(o.into()),
+            MixedCow::Borrowed(r) => MixedRef::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<MixedRefMut<'a, T>> for MixedCowMut<'a, T>
+    where Box<T>: Into<T::Owned>
+{
+    fn from(r: MixedRefMut<'a, T>) -> Self {
+        match r {
+            MixedRefMut::Owned(b) => MixedCowMut::Owned(b.into()),
+            MixedRefMut::Borrowed(r) => MixedCowMut::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<MixedCowMut<'a, T>> for MixedRefMut<'a, T>
+    where T::Owned: Into<Box<T>>
+{
+    fn from(c: MixedCowMut<'a, T>) -> Self {
+        match c {
+            MixedCowMut::Owned(o) => MixedRefMut::Owned(o.into()),
+            MixedCowMut::Borrowed(r) => MixedRefMut::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<Cow<'a, T>> for MixedCow<'a, T> {
+    fn from(cow: Cow<'a, T>) -> Self {
+        match cow {
+            Cow::Owned(o) => MixedCow::Owned(o),
+            Cow::Borrowed(r) => MixedCow::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> From<MixedCow<'a, T>> for Cow<'a, T> {
+    fn from(c: MixedCow<'a, T>) -> Self {
+        match c {
+            MixedCow::Owned(o) => Cow::Owned(o),
+            MixedCow::Borrowed(r) => Cow::Borrowed(r),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Deref for MixedCow<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match *self {
+            MixedCow::Owned(ref o) => o.borrow(),
+            MixedCow::Borrowed(r) => r,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Deref for MixedCowMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match *self {
+            MixedCowMut::Owned(ref o) => o.borrow(),
+            MixedCowMut::Borrowed(ref r) => r,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> DerefMut for MixedCowMut<'a, T>
+    where T::Owned: BorrowMut<T>
+{
+    fn deref_mut(&mut self) -> &mut T {
+        match *self {
+            MixedCowMut::Owned(ref mut o) => o.borrow_mut(),
+            MixedCowMut::Borrowed(ref mut r) => r,
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> AsRef<T> for MixedCow<'a, T> {
+    fn as_ref(&self) -> &T { self }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> AsRef<T> for MixedCowMut<'a, T> {
+    fn as_ref(&self) -> &T { self }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> AsMut<T> for MixedCowMut<'a, T>
+    where T::Owned: BorrowMut<T>
+{
+    fn as_mut(&mut self) -> &mut T { self }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Borrow<T> for MixedCow<'a, T> {
+    fn borrow(&self) -> &T { self }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> Borrow<T> for MixedCowMut<'a, T> {
+    fn borrow(&self) -> &T { self }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> BorrowMut<T> for MixedCowMut<'a, T>
+    where T::Owned: BorrowMut<T>
+{
+    fn borrow_mut(&mut self) -> &mut T { self }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized + ToOwned + PartialEq> PartialEq<T> for MixedCow<'a, U> {
+    fn eq(&self, other: &T) -> bool {
+        (self as &U) == other.as_ref()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized + ToOwned + PartialEq> PartialEq<T> for MixedCowMut<'a, U> {
+    fn eq(&self, other: &T) -> bool {
+        (self as &U) == other.as_ref()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> MixedCow<'a, T> {
+    /// Returns a mutable reference to the owned data, cloning the borrowed
+    /// data into an owned value in place if necessary.
+    ///
+    /// This is analogous to [`Cow::to_mut`].
+    ///
+    /// [`Cow::to_mut`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.to_mut
+    pub fn to_mut(&mut self) -> &mut T
+        where T::Owned: BorrowMut<T>
+    {
+        if let MixedCow::Borrowed(r) = *self {
+            *self = MixedCow::Owned(r.to_owned());
+        }
+        match *self {
+            MixedCow::Owned(ref mut o) => o.borrow_mut(),
+            MixedCow::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// Extracts the owned data, cloning the borrowed data if necessary.
+    ///
+    /// This is analogous to [`Cow::into_owned`].
+    ///
+    /// [`Cow::into_owned`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.into_owned
+    pub fn into_owned(self) -> T::Owned {
+        match self {
+            MixedCow::Owned(o) => o,
+            MixedCow::Borrowed(r) => r.to_owned(),
+        }
+    }
+
+    /// Returns `true` if `self` holds owned data.
+    pub fn is_owned(&self) -> bool {
+        match *self {
+            MixedCow::Owned(_) => true,
+            MixedCow::Borrowed(_) => false,
+        }
+    }
+
+    /// Returns `true` if `self` holds borrowed data.
+    pub fn is_borrowed(&self) -> bool {
+        !self.is_owned()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a, T: ?Sized + ToOwned> MixedCowMut<'a, T> {
+    /// Extracts the owned data, cloning the borrowed data if necessary.
+    ///
+    /// This is analogous to [`Cow::into_owned`].
+    ///
+    /// [`Cow::into_owned`]: https://doc.rust-lang.org/std/borrow/enum.Cow.html#method.into_owned
+    pub fn into_owned(self) -> T::Owned {
+        match self {
+            MixedCowMut::Owned(o) => o,
+            MixedCowMut::Borrowed(r) => r.to_owned(),
+        }
+    }
+
+    /// Returns `true` if `self` holds owned data.
+    pub fn is_owned(&self) -> bool {
+        match *self {
+            MixedCowMut::Owned(_) => true,
+            MixedCowMut::Borrowed(_) => false,
+        }
+    }
+
+    /// Returns `true` if `self` holds borrowed data.
+    pub fn is_borrowed(&self) -> bool {
+        !self.is_owned()
+    }
+
+    /// Downcasts `self` into a reference to immutable data.
+    pub fn downcast(self) -> MixedCow<'a, T> {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MixedRef, MixedRefMut};
+    use core::cmp::Ordering;
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    use super::{MixedCow, MixedCowMut};
+
+    #[cfg(all(not(feature = "std"), feature = "alloc"))]
+    use alloc::boxed::Box;
+    #[cfg(all(not(feature = "std"), feature = "alloc"))]
+    use alloc::string::String;
+    #[cfg(all(not(feature = "std"), feature = "alloc"))]
+    use alloc::vec::Vec;
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    use super::Cow;
+
+    /// Generic over `impl Into<MixedRef<'a, T>>`, as recommended in the
+    /// crate docs, so a single call site accepts every owned representation.
+    fn accept<'a, T: ?Sized + 'a>(value: impl Into<MixedRef<'a, T>>) -> MixedRef<'a, T> {
+        value.into()
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn into_mixed_ref_from_owned_representations() {
+        let from_box: MixedRef<str> = accept(Box::<str>::from("Ferris"));
+        assert!(from_box.is_owned());
+        assert_eq!(&*from_box, "Ferris");
+
+        let from_string: MixedRef<str> = accept(String::from("Ferris"));
+        assert!(from_string.is_owned());
+        assert_eq!(&*from_string, "Ferris");
+
+        let v: Vec<u8> = Vec::from([1u8, 2, 3]);
+        let from_vec: MixedRef<[u8]> = accept(v);
+        assert!(from_vec.is_owned());
+        assert_eq!(&*from_vec, [1, 2, 3]);
+    }
+
+    #[cfg(not(any(feature = "std", feature = "alloc")))]
+    #[test]
+    fn into_mixed_ref_stays_borrowed_without_an_allocator() {
+        let name = "Ferris";
+        let value: MixedRef<str> = accept(name);
+        assert!(value.is_borrowed());
+        assert_eq!(&*value, "Ferris");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn add_assign_promotes_borrowed_then_reuses_owned() {
+        let mut value: MixedRef<str> = MixedRef::Borrowed("Hello, ");
+        assert!(value.is_borrowed());
+
+        value += "Ferris";
+        assert!(value.is_owned());
+        assert_eq!(&*value, "Hello, Ferris");
+
+        // Once `Owned`, further appends must reuse the same variant rather
+        // than falling back to the `Borrowed` promotion path.
+        value += "!";
+        assert!(value.is_owned());
+        assert_eq!(&*value, "Hello, Ferris!");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn add_assign_with_owned_rhs() {
+        let mut value: MixedRef<str> = MixedRef::Borrowed("Hello, ");
+        value += String::from("Ferris");
+        assert_eq!(&*value, "Hello, Ferris");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn add_returns_owned_concatenation() {
+        let value: MixedRef<str> = MixedRef::Borrowed("Hello, ") + "Ferris";
+        assert!(value.is_owned());
+        assert_eq!(&*value, "Hello, Ferris");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn write_fmt_appends_through_fmt_write() {
+        use core::fmt::Write;
+
+        let mut value: MixedRef<str> = MixedRef::Borrowed("count: ");
+        write!(value, "{}", 42).unwrap();
+        assert!(value.is_owned());
+        assert_eq!(&*value, "count: 42");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn mixed_cow_round_trips_through_mixed_ref() {
+        let owned: MixedCow<str> = MixedCow::Owned(String::from("Ferris"));
+        let borrowed: MixedCow<str> = MixedCow::Borrowed("Ferris");
+
+        let owned_ref: MixedRef<str> = owned.into();
+        assert!(owned_ref.is_owned());
+        assert_eq!(&*owned_ref, "Ferris");
+
+        let borrowed_ref: MixedRef<str> = borrowed.into();
+        assert!(borrowed_ref.is_borrowed());
+        assert_eq!(&*borrowed_ref, "Ferris");
+
+        let back: MixedCow<str> = owned_ref.into();
+        assert!(back.is_owned());
+        assert_eq!(&*back, "Ferris");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn mixed_cow_round_trips_through_std_cow() {
+        let owned: MixedCow<str> = MixedCow::Owned(String::from("Ferris"));
+        let borrowed: MixedCow<str> = MixedCow::Borrowed("Ferris");
+
+        let owned_cow: Cow<str> = owned.into();
+        assert!(matches!(owned_cow, Cow::Owned(_)));
+        assert_eq!(&*owned_cow, "Ferris");
+
+        let borrowed_cow: Cow<str> = borrowed.into();
+        assert!(matches!(borrowed_cow, Cow::Borrowed(_)));
+        assert_eq!(&*borrowed_cow, "Ferris");
+
+        let back: MixedCow<str> = owned_cow.into();
+        assert!(back.is_owned());
+        assert_eq!(&*back, "Ferris");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn mixed_cow_to_mut_promotes_borrowed_and_reuses_owned() {
+        let mut borrowed: MixedCow<str> = MixedCow::Borrowed("Ferris");
+        borrowed.to_mut().make_ascii_uppercase();
+        assert!(borrowed.is_owned());
+        assert_eq!(&*borrowed, "FERRIS");
+
+        let mut owned: MixedCow<str> = MixedCow::Owned(String::from("Ferris"));
+        owned.to_mut().make_ascii_uppercase();
+        assert!(owned.is_owned());
+        assert_eq!(&*owned, "FERRIS");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn mixed_cow_into_owned_clones_only_when_borrowed() {
+        let owned: MixedCow<str> = MixedCow::Owned(String::from("Ferris"));
+        assert_eq!(owned.into_owned(), String::from("Ferris"));
+
+        let borrowed: MixedCow<str> = MixedCow::Borrowed("Ferris");
+        assert_eq!(borrowed.into_owned(), String::from("Ferris"));
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn to_mut_promotes_borrowed_and_reuses_owned() {
+        let mut borrowed: MixedRef<str> = MixedRef::Borrowed("Ferris");
+        borrowed.to_mut().make_ascii_uppercase();
+        assert!(borrowed.is_owned());
+        assert_eq!(&*borrowed, "FERRIS");
+
+        let mut owned: MixedRef<str> = MixedRef::Owned(Box::from("Ferris"));
+        owned.to_mut().make_ascii_uppercase();
+        assert!(owned.is_owned());
+        assert_eq!(&*owned, "FERRIS");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn into_owned_clones_only_when_borrowed() {
+        let owned: MixedRef<str> = MixedRef::Owned(Box::from("Ferris"));
+        assert_eq!(&*owned.into_owned(), "Ferris");
+
+        let borrowed: MixedRef<str> = MixedRef::Borrowed("Ferris");
+        assert_eq!(&*borrowed.into_owned(), "Ferris");
+
+        let mut name = String::from("Ferris");
+        let owned_mut: MixedRefMut<str> = MixedRefMut::Owned(Box::from("Ferris"));
+        assert_eq!(&*owned_mut.into_owned(), "Ferris");
+
+        let borrowed_mut: MixedRefMut<str> = MixedRefMut::Borrowed(&mut name);
+        assert_eq!(&*borrowed_mut.into_owned(), "Ferris");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn clone_deep_copies_owned_and_shares_borrowed() {
+        let owned: MixedRef<str> = MixedRef::Owned(Box::from("Ferris"));
+        let owned_clone = owned.clone();
+        assert!(owned_clone.is_owned());
+        assert_eq!(&*owned_clone, "Ferris");
+
+        let borrowed: MixedRef<str> = MixedRef::Borrowed("Ferris");
+        let borrowed_clone = borrowed.clone();
+        assert!(borrowed_clone.is_borrowed());
+        assert_eq!(&*borrowed_clone, "Ferris");
+    }
+
+    #[test]
+    fn ord_compares_by_dereferenced_value() {
+        let a: MixedRef<str> = MixedRef::Borrowed("a");
+        let b: MixedRef<str> = MixedRef::Borrowed("b");
+        assert!(a < b);
+        assert_eq!(a.cmp(&a), Ordering::Equal);
+
+        let mut x = 1;
+        let mut y = 2;
+        let a_mut: MixedRefMut<i32> = MixedRefMut::Borrowed(&mut x);
+        let b_mut: MixedRefMut<i32> = MixedRefMut::Borrowed(&mut y);
+        assert!(a_mut < b_mut);
+    }
+
+    #[test]
+    fn partial_ord_compares_across_mixed_ref_and_mixed_ref_mut() {
+        let x = 1;
+        let mut y = 2;
+        let a: MixedRef<i32> = MixedRef::Borrowed(&x);
+        let b_mut: MixedRefMut<i32> = MixedRefMut::Borrowed(&mut y);
+
+        assert_eq!(a.partial_cmp(&b_mut), Some(Ordering::Less));
+        assert!(a < b_mut);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn mixed_cow_mut_deref_mut_and_downcast() {
+        let mut first = String::from("Ferris");
+        let mut value: MixedCowMut<str> = MixedCowMut::Borrowed(&mut first);
+        value.make_ascii_uppercase();
+        assert_eq!(&*value, "FERRIS");
+        assert!(value.is_borrowed());
+
+        let owned: MixedCowMut<str> = MixedCowMut::Owned(String::from("Ferris"));
+        assert_eq!(owned.into_owned(), String::from("Ferris"));
+
+        let mut second = String::from("Ferris");
+        let borrowed: MixedCowMut<str> = MixedCowMut::Borrowed(&mut second);
+        let downcast: MixedCow<str> = borrowed.downcast();
+        assert!(downcast.is_borrowed());
+        assert_eq!(&*downcast, "Ferris");
+    }
+}